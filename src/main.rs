@@ -1,24 +1,44 @@
 use actix_web::{
     App, Error, HttpResponse, HttpServer, Responder,
-    web::{self, Form},
+    web::{self, Form, Json},
 };
 use anyhow::{Context, Result, bail};
 use askama::Template;
 use askama_actix::TemplateToResponse;
+use async_stream::stream;
 use chrono::{NaiveDateTime, Utc};
+use lofty::file::{AudioFile, TaggedFileExt};
+use lofty::tag::Accessor;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
 use serde::Deserialize;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
 use std::path::{Path, PathBuf};
 use tokio::fs;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 const MUSIC_DIRECTORY: &str = "./music";
 const DATABASE_URL: &str = "sqlite:db/votes.db";
+const COVERS_DIRECTORY: &str = "./covers";
+// "weighted" switches next_song_handler to softmax-weighted random selection; anything else
+// (including unset) keeps the deterministic greedy top-score pick.
+const SELECTION_MODE_ENV: &str = "DT_SELECTION_MODE";
+const SELECTION_TEMPERATURE_ENV: &str = "DT_SELECTION_TEMPERATURE";
+const DEFAULT_SELECTION_TEMPERATURE: f64 = 2.0;
+// How many top-scoring unplayed songs the weighted draw is sampled from.
+const SELECTION_POOL_SIZE: i64 = 10;
+// Set to enable GET /metrics on the main server; unset by default since most deployments
+// don't want scoreboard internals exposed publicly.
+const METRICS_ENABLED_ENV: &str = "DT_METRICS_ENABLED";
 
 #[derive(Template)]
 #[template(path = "host.html")]
 struct Host {
     songs: Vec<Song>,
+    now_playing: Option<NowPlayingStatus>,
 }
 
 #[derive(Template)]
@@ -26,6 +46,17 @@ struct Host {
 struct Queue {
     candidates: Vec<Candidate>,
     voter_id: Uuid,
+    slug: String,
+}
+
+// Renders per-song voter attribution for a room: who's championing what's up next, and who's
+// behind the song currently playing.
+#[derive(Template)]
+#[template(path = "status.html")]
+struct Status {
+    slug: String,
+    now_playing: Option<NowPlayingStatus>,
+    candidates: Vec<CandidateStatus>,
 }
 
 /*
@@ -42,30 +73,89 @@ struct CandidateCard {
 struct CandidateList {
     candidates: Vec<Candidate>,
     voter_id: Uuid,
+    slug: String,
 }
 
 #[derive(Clone, Debug, sqlx::FromRow)]
 struct Song {
     id: String,
     name: String,
+    artist: Option<String>,
+    album: Option<String>,
+    duration_secs: Option<i64>,
+    cover_path: Option<String>,
     played_at: Option<NaiveDateTime>,
 }
 
 // Candidate represents a song in the voting queue
-#[derive(Clone, Debug, sqlx::FromRow)]
+#[derive(Clone, Debug, serde::Serialize, sqlx::FromRow)]
 struct Candidate {
     id: String, // Use the filename ID consistent with Song
     name: String,
+    artist: Option<String>,
+    album: Option<String>,
+    cover_path: Option<String>,
     #[sqlx(default)] // Default to None if the voter hasn't voted for this song
     voter_decision: Option<i64>,
 }
 
+// Metadata pulled from embedded ID3v2/Vorbis/MP4 tags by sync_songs_to_db, falling back to the
+// filename stem when a file carries no usable tag.
+struct TrackMetadata {
+    name: String,
+    artist: Option<String>,
+    album: Option<String>,
+    duration_secs: Option<i64>,
+    cover_path: Option<String>,
+}
+
 #[derive(Clone, Debug, serde::Serialize, sqlx::FromRow)]
 struct NextSongInfo {
     id: String,
     name: String,
 }
 
+// An unplayed song and its summed vote score, as considered by the next-song selection pool.
+#[derive(Clone, Debug, sqlx::FromRow)]
+struct ScoredSong {
+    id: String,
+    name: String,
+    total_score: f64,
+}
+
+// How next_song_handler picks among the top-scoring unplayed candidates. Greedy always takes
+// the single highest score; WeightedRandom softmax-samples the pool so lower-scored songs
+// aren't starved and ties aren't broken arbitrarily.
+#[derive(Clone, Copy, Debug)]
+enum SelectionMode {
+    Greedy,
+    WeightedRandom { temperature: f64 },
+}
+
+// Uniform envelope for the /api/v1 surface, so a decoupled client always gets the same
+// type/content shape regardless of which endpoint it called.
+#[derive(Debug, serde::Serialize)]
+#[serde(tag = "type", content = "content")]
+enum ApiResponse<T> {
+    Success(T),
+    Failure(String),
+    Fatal(String),
+}
+
+impl<T: serde::Serialize> ApiResponse<T> {
+    fn success(value: T) -> HttpResponse {
+        HttpResponse::Ok().json(ApiResponse::Success(value))
+    }
+
+    fn failure(message: impl Into<String>) -> HttpResponse {
+        HttpResponse::BadRequest().json(ApiResponse::<T>::Failure(message.into()))
+    }
+
+    fn fatal(message: impl Into<String>) -> HttpResponse {
+        HttpResponse::InternalServerError().json(ApiResponse::<T>::Fatal(message.into()))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct Vote {
     decision: i8,
@@ -73,8 +163,123 @@ struct Vote {
     song_id: String,
 }
 
+// A room is an independent voting lobby: its own scored queue, partitioned from every
+// other room by room_id on votes.
+#[derive(Clone, Debug, sqlx::FromRow)]
+struct Room {
+    id: String,
+    slug: String,
+    pin: Option<String>,
+    created_at: NaiveDateTime,
+}
+
+// A single voter's summed positive contribution to a song's score, for "championed by"
+// attribution. Only upvotes count towards this; downvotes don't make someone a supporter.
+#[derive(Clone, Debug, serde::Serialize)]
+struct Supporter {
+    voter_id: Uuid,
+    display_name: String,
+    score: i64,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+struct NowPlayingStatus {
+    song: NextSongInfo,
+    total_score: i64,
+    supporters: Vec<Supporter>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+struct CandidateStatus {
+    id: String,
+    name: String,
+    total_score: i64,
+    supporters: Vec<Supporter>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+struct RoomStatus {
+    now_playing: Option<NowPlayingStatus>,
+    candidates: Vec<CandidateStatus>,
+}
+
 struct AppState {
     db_pool: SqlitePool,
+    queue_events: broadcast::Sender<QueueEvent>,
+    metrics: Metrics,
+    selection_mode: SelectionMode,
+}
+
+// Prometheus handles are themselves cheap Arc wrappers, so Metrics can be cloned into every
+// worker like the db_pool and queue_events already are.
+#[derive(Clone)]
+struct Metrics {
+    registry: Registry,
+    votes_total: IntCounterVec,
+    songs_played_total: IntCounter,
+    unplayed_songs_total: IntGauge,
+    candidate_query_latency: Histogram,
+}
+
+impl Metrics {
+    fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let votes_total = IntCounterVec::new(
+            Opts::new("dt_votes_total", "Total votes cast, labeled by decision sign"),
+            &["sign"],
+        )?;
+        let songs_played_total = IntCounter::new(
+            "dt_songs_played_total",
+            "Total number of songs marked as played",
+        )?;
+        // Songs not yet played in ANY room. Play state is now tracked per room
+        // (room_song_plays), so this is not a per-room queue depth: a song played in one room
+        // but not others still drops out of this count.
+        let unplayed_songs_total = IntGauge::new(
+            "dt_unplayed_songs_total",
+            "Number of songs not yet played in any room",
+        )?;
+        let candidate_query_latency = Histogram::with_opts(HistogramOpts::new(
+            "dt_candidate_list_query_seconds",
+            "Latency of the scored candidate list query",
+        ))?;
+
+        registry.register(Box::new(votes_total.clone()))?;
+        registry.register(Box::new(songs_played_total.clone()))?;
+        registry.register(Box::new(unplayed_songs_total.clone()))?;
+        registry.register(Box::new(candidate_query_latency.clone()))?;
+
+        Ok(Self {
+            registry,
+            votes_total,
+            songs_played_total,
+            unplayed_songs_total,
+            candidate_query_latency,
+        })
+    }
+
+    fn record_vote(&self, decision: i8) {
+        let sign = if decision >= 0 { "up" } else { "down" };
+        self.votes_total.with_label_values(&[sign]).inc();
+    }
+}
+
+// Broadcast over this instead of leaving clients to poll /r/{slug}/queue.
+#[derive(Clone, Debug)]
+enum QueueEvent {
+    VoteCast { room_id: String, song_id: String },
+    SongPlayed { room_id: String, song_id: String },
+}
+
+impl QueueEvent {
+    fn room_id(&self) -> &str {
+        match self {
+            QueueEvent::VoteCast { room_id, .. } | QueueEvent::SongPlayed { room_id, .. } => {
+                room_id
+            }
+        }
+    }
 }
 
 impl Candidate {
@@ -91,6 +296,88 @@ impl Candidate {
 
 // --- Database Functions ---
 
+// Reads embedded ID3v2/Vorbis/MP4 tags off an audio file, caching any embedded cover art under
+// COVERS_DIRECTORY. Falls back to fallback_name (the filename stem) and no artwork when the
+// file has no usable tag.
+async fn extract_track_metadata(path: &Path, id: &str, fallback_name: &str) -> TrackMetadata {
+    let path = path.to_owned();
+    let id = id.to_string();
+    let fallback_name = fallback_name.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        let tagged_file = match lofty::read_from_path(&path) {
+            Ok(f) => f,
+            Err(e) => {
+                log::warn!("Failed to read tags from {path:?}: {e}");
+                return TrackMetadata {
+                    name: fallback_name,
+                    artist: None,
+                    album: None,
+                    duration_secs: None,
+                    cover_path: None,
+                };
+            }
+        };
+
+        let duration_secs = Some(tagged_file.properties().duration().as_secs() as i64);
+        let tag = tagged_file.primary_tag().or_else(|| tagged_file.first_tag());
+
+        let name = tag
+            .and_then(|tag| tag.title())
+            .map(|title| title.to_string())
+            .unwrap_or(fallback_name);
+        let artist = tag.and_then(|tag| tag.artist()).map(|a| a.to_string());
+        let album = tag.and_then(|tag| tag.album()).map(|a| a.to_string());
+        let cover_path = tag
+            .and_then(|tag| tag.pictures().first())
+            .and_then(|picture| save_cover_art(&id, picture));
+
+        TrackMetadata {
+            name,
+            artist,
+            album,
+            duration_secs,
+            cover_path,
+        }
+    })
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Tag extraction task panicked: {e}");
+        TrackMetadata {
+            name: fallback_name.clone(),
+            artist: None,
+            album: None,
+            duration_secs: None,
+            cover_path: None,
+        }
+    })
+}
+
+// Caches an embedded cover picture to COVERS_DIRECTORY/{id}.{ext} and returns its relative path,
+// or None if the image couldn't be written.
+fn save_cover_art(id: &str, picture: &lofty::picture::Picture) -> Option<String> {
+    let ext = picture
+        .mime_type()
+        .map(|mime| match mime {
+            lofty::picture::MimeType::Png => "png",
+            lofty::picture::MimeType::Jpeg => "jpg",
+            lofty::picture::MimeType::Gif => "gif",
+            lofty::picture::MimeType::Bmp => "bmp",
+            _ => "bin",
+        })
+        .unwrap_or("bin");
+
+    let relative_path = format!("{id}.{ext}");
+    let full_path = Path::new(COVERS_DIRECTORY).join(&relative_path);
+
+    if let Err(e) = std::fs::write(&full_path, picture.data()) {
+        log::warn!("Failed to write cover art for {id} to {full_path:?}: {e}");
+        return None;
+    }
+
+    Some(relative_path)
+}
+
 // Function to sync songs from the directory to the database
 async fn sync_songs_to_db(music_dir: &Path, pool: &SqlitePool) -> Result<()> {
     log::info!("Starting song sync from directory: {music_dir:?}");
@@ -117,7 +404,7 @@ async fn sync_songs_to_db(music_dir: &Path, pool: &SqlitePool) -> Result<()> {
                     {
                         songs_found_in_dir += 1;
                         let id = filename_str.to_string();
-                        let name = path
+                        let fallback_name = path
                             .file_stem()
                             .unwrap_or_default() // Handle potential panic
                             .to_str()
@@ -126,11 +413,20 @@ async fn sync_songs_to_db(music_dir: &Path, pool: &SqlitePool) -> Result<()> {
 
                         // If song is not in DB, insert it
                         if !songs_in_db.contains(&id) {
-                            log::info!("Adding new song to DB: ID={id}, Name={name}");
+                            let metadata = extract_track_metadata(&path, &id, &fallback_name).await;
+                            log::info!("Adding new song to DB: ID={id}, Name={}", metadata.name);
 
-                            sqlx::query!("INSERT INTO songs (id, name) VALUES (?, ?)", id, name)
-                                .execute(pool)
-                                .await?;
+                            sqlx::query!(
+                                "INSERT INTO songs (id, name, artist, album, duration_secs, cover_path) VALUES (?, ?, ?, ?, ?, ?)",
+                                id,
+                                metadata.name,
+                                metadata.artist,
+                                metadata.album,
+                                metadata.duration_secs,
+                                metadata.cover_path
+                            )
+                            .execute(pool)
+                            .await?;
                             songs_added += 1;
                         } else {
                             // Remove from the set, remaining items will be deleted later
@@ -158,95 +454,296 @@ async fn sync_songs_to_db(music_dir: &Path, pool: &SqlitePool) -> Result<()> {
     Ok(())
 }
 
-// Function to get songs (now reads from DB)
-async fn get_songs_from_db(pool: &SqlitePool) -> Result<Vec<Song>> {
-    let songs = sqlx::query_as!(Song, "SELECT * FROM songs")
-        .fetch_all(pool)
-        .await?;
+// Function to get songs (now reads from DB), with each song's played_at resolved against a
+// single room's plays rather than a global column, since the same library is shared but play
+// state is per-room.
+async fn get_songs_from_db(pool: &SqlitePool, room_id: &str) -> Result<Vec<Song>> {
+    let songs = sqlx::query_as!(
+        Song,
+        r#"
+        SELECT
+            s.id,
+            s.name,
+            s.artist,
+            s.album,
+            s.duration_secs,
+            s.cover_path,
+            rsp.played_at as "played_at: NaiveDateTime"
+        FROM songs s
+        LEFT JOIN room_song_plays rsp ON rsp.song_id = s.id AND rsp.room_id = ?
+        ORDER BY s.name;
+        "#,
+        room_id
+    )
+    .fetch_all(pool)
+    .await?;
     Ok(songs)
 }
 
+// --- Rooms ---
+
+async fn get_room_by_slug(pool: &SqlitePool, slug: &str) -> Result<Option<Room>> {
+    let room = sqlx::query_as!(Room, "SELECT * FROM rooms WHERE slug = ?", slug)
+        .fetch_optional(pool)
+        .await?;
+    Ok(room)
+}
+
 // --- Handlers ---
 
-// Serves the host page (reads songs from DB)
-async fn host_page(data: web::Data<AppState>) -> Result<Host, Error> {
-    let songs = get_songs_from_db(&data.db_pool).await.map_err(|e| {
-        log::error!("Failed to get songs from DB: {e}");
-        actix_web::error::ErrorInternalServerError("Could not load songs")
-    })?;
-    Ok(Host { songs })
+// Renders a landing page where a host can spin up a fresh voting room.
+async fn index_handler() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head><meta charset="utf-8"><title>dt</title></head>
+<body>
+    <h1>dt</h1>
+    <form method="post" action="/rooms">
+        <button type="submit">Start a new room</button>
+    </form>
+</body>
+</html>"#,
+        )
 }
 
-// Endpoint to get the next song for the host player
-async fn next_song_handler(data: web::Data<AppState>) -> Result<HttpResponse, Error> {
+// Creates a fresh room and redirects the host straight to it.
+async fn create_room_handler(data: web::Data<AppState>) -> Result<HttpResponse, Error> {
+    let id = Uuid::new_v4().to_string();
+    let slug: String = id.chars().filter(|c| *c != '-').take(8).collect();
+
+    sqlx::query!("INSERT INTO rooms (id, slug) VALUES (?, ?)", id, slug)
+        .execute(&data.db_pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to create room: {e}");
+            actix_web::error::ErrorInternalServerError("Could not create room")
+        })?;
+
+    log::info!("Created room {slug} (id={id})");
+    Ok(HttpResponse::SeeOther()
+        .append_header(("Location", format!("/r/{slug}")))
+        .finish())
+}
+
+// Serves the host page for a room (reads songs from DB; the song library itself is shared
+// across rooms, while votes and play state are partitioned per room)
+async fn host_page(path: web::Path<String>, data: web::Data<AppState>) -> Result<Host, Error> {
+    let room = get_room_by_slug(&data.db_pool, &path)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to look up room {}: {e}", *path);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Room not found"))?;
+
+    let songs = get_songs_from_db(&data.db_pool, &room.id)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to get songs from DB: {e}");
+            actix_web::error::ErrorInternalServerError("Could not load songs")
+        })?;
+    let now_playing = get_now_playing_status(&data.db_pool, &room.id)
+        .await
+        .map_err(|e| {
+            log::error!(
+                "Failed to get now-playing status for room {}: {e}",
+                room.slug
+            );
+            actix_web::error::ErrorInternalServerError("Could not load now-playing status")
+        })?;
+    Ok(Host { songs, now_playing })
+}
+
+// Serves a song's extracted cover art, cached under COVERS_DIRECTORY by extract_track_metadata.
+async fn cover_handler(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> Result<actix_files::NamedFile, Error> {
+    let id = path.into_inner();
+
+    let cover_path = sqlx::query_scalar!("SELECT cover_path FROM songs WHERE id = ?", id)
+        .fetch_optional(&data.db_pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to look up cover art for song {id}: {e}");
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .flatten()
+        .ok_or_else(|| actix_web::error::ErrorNotFound("No cover art for this song"))?;
+
+    actix_files::NamedFile::open(Path::new(COVERS_DIRECTORY).join(cover_path))
+        .map_err(|e| {
+            log::warn!("Cover art file missing for song {id}: {e}");
+            actix_web::error::ErrorNotFound("Cover art not found")
+        })
+}
+
+// Picks a song from the top-scoring unplayed pool according to the configured selection mode.
+fn pick_song(candidates: &[ScoredSong], mode: SelectionMode) -> Option<&ScoredSong> {
+    match mode {
+        SelectionMode::Greedy => candidates.first(),
+        SelectionMode::WeightedRandom { temperature } => weighted_pick(candidates, temperature),
+    }
+}
+
+// Softmax-weighted random draw over candidates: w_i = exp((score_i - max_score) / temperature),
+// normalized and sampled via a single uniform draw over the cumulative distribution. Shifting
+// by max_score before exp() avoids overflow without changing the resulting probabilities.
+fn weighted_pick(candidates: &[ScoredSong], temperature: f64) -> Option<&ScoredSong> {
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let max_score = candidates
+        .iter()
+        .map(|c| c.total_score)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let weights: Vec<f64> = candidates
+        .iter()
+        .map(|c| ((c.total_score - max_score) / temperature).exp())
+        .collect();
+    let total_weight: f64 = weights.iter().sum();
+
+    let mut draw = rand::random::<f64>() * total_weight;
+    for (candidate, weight) in candidates.iter().zip(weights.iter()) {
+        draw -= weight;
+        if draw <= 0.0 {
+            return Some(candidate);
+        }
+    }
+
+    // Floating-point rounding can leave a sliver of probability mass unconsumed; fall back to
+    // the last candidate rather than returning None for an otherwise-valid draw.
+    candidates.last()
+}
+
+// Picks the next song from the top-scoring unplayed pool for a room, marks it played, and
+// broadcasts a SongPlayed event. Shared by the HTML host endpoint and the JSON /api/v1 surface.
+async fn select_and_mark_next_song(
+    data: &AppState,
+    room: &Room,
+) -> Result<Option<NextSongInfo>> {
     let pool = &data.db_pool;
 
     // Start a transaction
-    let mut tx = pool.begin().await.map_err(|e| {
-        log::error!("Failed to begin transaction: {e}");
-        actix_web::error::ErrorInternalServerError("Database error")
-    })?;
+    let mut tx = pool.begin().await?;
 
-    // Find the top-scoring song that hasn't been played (played_at IS NULL)
-    let next_song_candidate = sqlx::query_as!(
-        NextSongInfo,
+    // Pull the top-scoring candidates this room hasn't already played, and let the configured
+    // selection mode choose among them. Played state lives in room_song_plays, not on songs
+    // itself, so a pick in one room never removes a song from another room's pool.
+    let pool_candidates = sqlx::query_as!(
+        ScoredSong,
         r#"
         SELECT
             s.id as "id!",
-            s.name as "name!"
+            s.name as "name!",
+            COALESCE(v.total_score, 0) as "total_score!: f64"
         FROM songs s
         LEFT JOIN (
             SELECT song_id, SUM(decision) as total_score
             FROM votes
+            WHERE room_id = ?
             GROUP BY song_id
         ) v ON s.id = v.song_id
-        WHERE s.played_at IS NULL  -- Only select songs that haven't been played
+        WHERE NOT EXISTS (
+            SELECT 1 FROM room_song_plays rsp
+            WHERE rsp.room_id = ? AND rsp.song_id = s.id
+        )
         ORDER BY COALESCE(v.total_score, 0) DESC
+        LIMIT ?;
+        "#,
+        room.id,
+        room.id,
+        SELECTION_POOL_SIZE
+    )
+    .fetch_all(&mut *tx) // Use the transaction
+    .await?;
+
+    let next_song_candidate = pick_song(&pool_candidates, data.selection_mode).map(|song| {
+        NextSongInfo {
+            id: song.id.clone(),
+            name: song.name.clone(),
+        }
+    });
+
+    let Some(song) = next_song_candidate else {
+        // No need to commit/rollback as nothing was changed
+        return Ok(None);
+    };
+
+    let now = Utc::now();
+    sqlx::query!(
+        "INSERT INTO room_song_plays (room_id, song_id, played_at) VALUES (?, ?, ?)",
+        room.id,
+        song.id,
+        now
+    )
+    .execute(&mut *tx) // Use the transaction
+    .await?;
+    tx.commit().await?;
+
+    log::info!("Marked song {} as played.", song.id);
+    data.metrics.songs_played_total.inc();
+    let _ = data.queue_events.send(QueueEvent::SongPlayed {
+        room_id: room.id.clone(),
+        song_id: song.id.clone(),
+    });
+
+    Ok(Some(song))
+}
+
+// Looks up the most recently played song for a single room. Play state is tracked per room
+// in room_song_plays, so two rooms can have entirely different (or no) song playing.
+async fn get_now_playing(pool: &SqlitePool, room_id: &str) -> Result<Option<NextSongInfo>> {
+    let song = sqlx::query_as!(
+        NextSongInfo,
+        r#"
+        SELECT s.id as "id!", s.name as "name!"
+        FROM room_song_plays rsp
+        JOIN songs s ON s.id = rsp.song_id
+        WHERE rsp.room_id = ?
+        ORDER BY rsp.played_at DESC
         LIMIT 1;
-        "#
+        "#,
+        room_id
     )
-    .fetch_optional(&mut *tx) // Use the transaction
-    .await
-    .map_err(|e| {
-        log::error!("Failed to query next song: {e}");
-        actix_web::error::ErrorInternalServerError("Database error finding next song")
-    })?;
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(song)
+}
+
+// Endpoint to get the next song for the host player, scored from a single room's votes
+async fn next_song_handler(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse, Error> {
+    let room = get_room_by_slug(&data.db_pool, &path)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to look up room {}: {e}", *path);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Room not found"))?;
 
-    match next_song_candidate {
-        Some(song) => {
+    match select_and_mark_next_song(&data, &room).await {
+        Ok(Some(song)) => {
             log::debug!("Next song selected: ID={}, Name={}", song.id, song.name);
-            let now = Utc::now();
-            let update_result =
-                sqlx::query!("UPDATE songs SET played_at = ? WHERE id = ?", now, song.id)
-                    .execute(&mut *tx) // Use the transaction
-                    .await;
-
-            match update_result {
-                Ok(_) => {
-                    // Commit the transaction
-                    tx.commit().await.map_err(|e| {
-                        log::error!("Failed to commit transaction: {e}");
-                        actix_web::error::ErrorInternalServerError(
-                            "Database error saving play status",
-                        )
-                    })?;
-                    log::info!("Marked song {} as played.", song.id);
-                    Ok(HttpResponse::Ok().json(song)) // Return song info as JSON
-                }
-                Err(e) => {
-                    log::error!("Failed to mark song {} as played: {}", song.id, e);
-                    // Rollback implicitly handled by drop, but good practice to log
-                    Err(actix_web::error::ErrorInternalServerError(
-                        "Database error updating play status",
-                    ))
-                }
-            }
+            Ok(HttpResponse::Ok().json(song)) // Return song info as JSON
         }
-        None => {
+        Ok(None) => {
             log::warn!("No unplayed songs found in the queue.");
-            // No need to commit/rollback as nothing was changed
             Ok(HttpResponse::NotFound().body("No unplayed songs available"))
         }
+        Err(e) => {
+            log::error!("Failed to select next song for room {}: {e}", room.slug);
+            Err(actix_web::error::ErrorInternalServerError(
+                "Database error finding next song",
+            ))
+        }
     }
 }
 
@@ -280,7 +777,42 @@ fn ensure_voter_id_cookie(
 
 // --- Queue & Voting Logic ---
 
-async fn get_candidates_with_scores(pool: &SqlitePool, voter_id: Uuid) -> Result<Vec<Candidate>> {
+// Inserts or updates a voter's decision on a song within a room. Shared by the HTML vote
+// handler and the JSON /api/v1 surface so both go through the same upsert semantics.
+async fn record_vote(
+    pool: &SqlitePool,
+    room_id: &str,
+    voter_id: Uuid,
+    song_id: &str,
+    decision: i8,
+) -> Result<()> {
+    let voter_id_string = voter_id.to_string();
+    sqlx::query!(
+        r#"
+        INSERT INTO votes (voter_id, song_id, room_id, decision)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(voter_id, song_id, room_id) DO UPDATE SET
+            decision = excluded.decision,
+            timestamp = CURRENT_TIMESTAMP
+        "#,
+        voter_id_string,
+        song_id,
+        room_id,
+        decision
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn get_candidates_with_scores(
+    pool: &SqlitePool,
+    voter_id: Uuid,
+    room_id: &str,
+    metrics: &Metrics,
+) -> Result<Vec<Candidate>> {
+    let _timer = metrics.candidate_query_latency.start_timer();
     let voter_id_str = voter_id.to_string();
 
     let candidates = sqlx::query_as!(
@@ -291,25 +823,35 @@ async fn get_candidates_with_scores(pool: &SqlitePool, voter_id: Uuid) -> Result
                 song_id,
                 SUM(decision) as total_score
             FROM votes
+            WHERE room_id = ?
             GROUP BY song_id
         ), VoterDecisions AS (
             SELECT
                 song_id,
                 decision
             FROM votes
-            WHERE voter_id = ?
+            WHERE voter_id = ? AND room_id = ?
         )
         SELECT
             s.id as "id!",
             s.name as "name!",
+            s.artist,
+            s.album,
+            s.cover_path,
             vd.decision as "voter_decision: i64"
         FROM songs s
         LEFT JOIN SongScores ss ON s.id = ss.song_id
         LEFT JOIN VoterDecisions vd ON s.id = vd.song_id
-        WHERE s.played_at IS NULL
+        WHERE NOT EXISTS (
+            SELECT 1 FROM room_song_plays rsp
+            WHERE rsp.room_id = ? AND rsp.song_id = s.id
+        )
         ORDER BY COALESCE(CAST(ss.total_score AS REAL), 0.0) DESC;
         "#,
-        voter_id_str
+        room_id,
+        voter_id_str,
+        room_id,
+        room_id
     )
     .fetch_all(pool)
     .await?;
@@ -317,17 +859,31 @@ async fn get_candidates_with_scores(pool: &SqlitePool, voter_id: Uuid) -> Result
     Ok(candidates)
 }
 
-// Serves the main voting queue page
-async fn queue_page(req: actix_web::HttpRequest, data: web::Data<AppState>) -> impl Responder {
+// Serves the main voting queue page for a room
+async fn queue_page(
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> impl Responder {
+    let room = match get_room_by_slug(&data.db_pool, &path).await {
+        Ok(Some(room)) => room,
+        Ok(None) => return HttpResponse::NotFound().body("Room not found"),
+        Err(e) => {
+            log::error!("Failed to look up room {}: {e}", *path);
+            return HttpResponse::InternalServerError().body("Database error");
+        }
+    };
+
     let mut jar = actix_web::cookie::CookieJar::new();
     // This function now ensures the jar has the cookie for the response
     let voter_id = ensure_voter_id_cookie(&req, &mut jar);
 
-    match get_candidates_with_scores(&data.db_pool, voter_id).await {
+    match get_candidates_with_scores(&data.db_pool, voter_id, &room.id, &data.metrics).await {
         Ok(candidates) => {
             let template = Queue {
                 candidates,
                 voter_id,
+                slug: room.slug.clone(),
             };
             let body = template.render().unwrap_or_else(|e| {
                 log::error!("Template rendering error: {e}");
@@ -353,11 +909,21 @@ async fn queue_page(req: actix_web::HttpRequest, data: web::Data<AppState>) -> i
     }
 }
 
-// Handler specifically for fetching and returning the queue partial content
+// Handler specifically for fetching and returning the queue partial content for a room
 async fn queue_content_handler(
     req: actix_web::HttpRequest,
+    path: web::Path<String>,
     data: web::Data<AppState>,
 ) -> impl Responder {
+    let room = match get_room_by_slug(&data.db_pool, &path).await {
+        Ok(Some(room)) => room,
+        Ok(None) => return HttpResponse::NotFound().body("Room not found"),
+        Err(e) => {
+            log::error!("Failed to look up room {}: {e}", *path);
+            return HttpResponse::InternalServerError().body("Database error");
+        }
+    };
+
     // 1. Get Voter ID from cookie (DO NOT try to set it here)
     let voter_id = req
         .cookie(VOTER_ID_COOKIE)
@@ -372,12 +938,13 @@ async fn queue_content_handler(
         });
 
     // 2. Fetch candidates using the retrieved voter ID
-    match get_candidates_with_scores(&data.db_pool, voter_id).await {
+    match get_candidates_with_scores(&data.db_pool, voter_id, &room.id, &data.metrics).await {
         Ok(candidates) => {
             //TODO investigate: should cookie jar be handled here as well?
             let partial = CandidateList {
                 candidates,
                 voter_id,
+                slug: room.slug.clone(),
             };
             partial.to_response()
         }
@@ -388,10 +955,127 @@ async fn queue_content_handler(
     }
 }
 
-// Handles votes and returns the updated candidate list partial
-async fn vote(data: web::Data<AppState>, Form(vote_data): Form<Vote>) -> impl Responder {
+// Renders the Prometheus registry in text exposition format. Gated behind METRICS_ENABLED_ENV
+// in main() since this endpoint has no auth of its own and isn't meant for public exposure.
+async fn metrics_handler(data: web::Data<AppState>) -> Result<HttpResponse, Error> {
+    // Songs not yet played in any room (see dt_unplayed_songs_total's doc comment): a single
+    // process-wide count, not any one room's queue depth.
+    let unplayed_songs_total = sqlx::query!(
+        r#"
+        SELECT COUNT(*) as count
+        FROM songs s
+        WHERE NOT EXISTS (SELECT 1 FROM room_song_plays rsp WHERE rsp.song_id = s.id)
+        "#
+    )
+    .fetch_one(&data.db_pool)
+    .await
+    .map(|row| row.count)
+    .map_err(|e| {
+        log::error!("Failed to query unplayed song count for /metrics: {e}");
+        actix_web::error::ErrorInternalServerError("Database error")
+    })?;
+    data.metrics.unplayed_songs_total.set(unplayed_songs_total);
+
+    let encoder = TextEncoder::new();
+    let metric_families = data.metrics.registry.gather();
+    let mut buffer = Vec::new();
+    encoder.encode(&metric_families, &mut buffer).map_err(|e| {
+        log::error!("Failed to encode metrics: {e}");
+        actix_web::error::ErrorInternalServerError("Failed to encode metrics")
+    })?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(encoder.format_type())
+        .body(buffer))
+}
+
+// Streams live queue updates over Server-Sent Events for a single room, replacing client-side
+// polling of queue_content_handler. Each subscriber re-renders its own scored view on every
+// broadcast tick for its room, using the voter ID captured from the cookie at connect time.
+async fn events_handler(
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> Result<HttpResponse, Error> {
+    let room = get_room_by_slug(&data.db_pool, &path)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to look up room {}: {e}", *path);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Room not found"))?;
+
+    let voter_id = req
+        .cookie(VOTER_ID_COOKIE)
+        .and_then(|cookie| Uuid::parse_str(cookie.value()).ok())
+        .unwrap_or_else(|| {
+            log::warn!("Voter ID cookie not found when opening event stream.");
+            Uuid::new_v4()
+        });
+
+    let mut rx = data.queue_events.subscribe();
+    let pool = data.db_pool.clone();
+    let metrics = data.metrics.clone();
+    let room_id = room.id.clone();
+    let slug = room.slug.clone();
+
+    let body = stream! {
+        loop {
+            // Lagged subscribers don't know which room(s) they missed updates for, so they
+            // always refresh; a normal tick only triggers a refresh for its own room.
+            let hit = match rx.recv().await {
+                Ok(event) if event.room_id() == room_id => true,
+                Ok(_) => false,
+                Err(broadcast::error::RecvError::Lagged(_)) => true,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if !hit {
+                continue;
+            }
+
+            match get_candidates_with_scores(&pool, voter_id, &room_id, &metrics).await {
+                Ok(candidates) => {
+                    let partial = CandidateList {
+                        candidates,
+                        voter_id,
+                        slug: slug.clone(),
+                    };
+                    match partial.render() {
+                        Ok(html) => {
+                            let payload = html.replace('\n', "");
+                            yield Ok::<_, Error>(web::Bytes::from(format!("data: {payload}\n\n")));
+                        }
+                        Err(e) => log::error!("Failed to render SSE candidate list: {e}"),
+                    }
+                }
+                Err(e) => log::error!("Failed to refresh candidates for SSE subscriber {voter_id}: {e}"),
+            }
+        }
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .append_header(("Cache-Control", "no-cache"))
+        .streaming(body))
+}
+
+// Handles votes for a room and returns the updated candidate list partial
+async fn vote(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+    Form(vote_data): Form<Vote>,
+) -> impl Responder {
     log::debug!("Received vote: {vote_data:?}");
 
+    let room = match get_room_by_slug(&data.db_pool, &path).await {
+        Ok(Some(room)) => room,
+        Ok(None) => return HttpResponse::NotFound().body("Room not found"),
+        Err(e) => {
+            log::error!("Failed to look up room {}: {e}", *path);
+            return HttpResponse::InternalServerError().body("Database error");
+        }
+    };
+
     let Vote {
         decision,
         voter_id,
@@ -404,31 +1088,24 @@ async fn vote(data: web::Data<AppState>, Form(vote_data): Form<Vote>) -> impl Re
             .body(format!("<p>Error saving vote with decision {decision}</p>"));
     }
 
-    let voter_id_string = voter_id.to_string();
-    // Insert or Update the vote in the database
-    let result = sqlx::query!(
-        r#"
-        INSERT INTO votes (voter_id, song_id, decision)
-        VALUES (?, ?, ?)
-        ON CONFLICT(voter_id, song_id) DO UPDATE SET
-            decision = excluded.decision,
-            timestamp = CURRENT_TIMESTAMP
-        "#,
-        voter_id_string,
-        song_id,
-        decision // Store decision directly
-    )
-    .execute(&data.db_pool)
-    .await;
+    let result = record_vote(&data.db_pool, &room.id, voter_id, &song_id, decision).await;
 
     match result {
-        Ok(_) => {
+        Ok(()) => {
             log::debug!("Vote recorded successfully for voter {voter_id} on song {song_id}");
+            data.metrics.record_vote(decision);
+            let _ = data.queue_events.send(QueueEvent::VoteCast {
+                room_id: room.id.clone(),
+                song_id: song_id.clone(),
+            });
 
-            match get_candidates_with_scores(&data.db_pool, voter_id).await {
+            match get_candidates_with_scores(&data.db_pool, voter_id, &room.id, &data.metrics)
+                .await
+            {
                 Ok(updated_candidates) => CandidateList {
                     candidates: updated_candidates,
                     voter_id,
+                    slug: room.slug.clone(),
                 }
                 .to_response(),
                 Err(e) => {
@@ -448,6 +1125,348 @@ async fn vote(data: web::Data<AppState>, Form(vote_data): Form<Vote>) -> impl Re
     }
 }
 
+// --- Status & Attribution ---
+
+// How many top supporters are surfaced per song on the status page and host "championed by"
+// line; deep supporter lists would just clutter both views.
+const STATUS_TOP_SUPPORTERS: i64 = 3;
+
+// A stable, anonymous-looking stand-in for voters who never set a nickname.
+fn short_voter_label(voter_id: Uuid) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    voter_id.hash(&mut hasher);
+    format!("voter-{:06x}", hasher.finish() & 0xFFFFFF)
+}
+
+// The top voters (by summed positive decision) behind a song in a room. Downvotes don't make
+// someone a supporter, so only decision > 0 counts towards attribution.
+async fn get_top_supporters(
+    pool: &SqlitePool,
+    room_id: &str,
+    song_id: &str,
+) -> Result<Vec<Supporter>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            v.voter_id as "voter_id!",
+            vt.display_name,
+            SUM(v.decision) as "score!: i64"
+        FROM votes v
+        LEFT JOIN voters vt ON vt.id = v.voter_id
+        WHERE v.room_id = ? AND v.song_id = ? AND v.decision > 0
+        GROUP BY v.voter_id
+        ORDER BY SUM(v.decision) DESC
+        LIMIT ?;
+        "#,
+        room_id,
+        song_id,
+        STATUS_TOP_SUPPORTERS
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let voter_id = Uuid::parse_str(&row.voter_id).ok()?;
+            let display_name = row
+                .display_name
+                .unwrap_or_else(|| short_voter_label(voter_id));
+            Some(Supporter {
+                voter_id,
+                display_name,
+                score: row.score,
+            })
+        })
+        .collect())
+}
+
+// The currently-playing song, scored and attributed against this room's votes. Now-playing
+// itself, the score, and the supporters behind it are all resolved per room.
+async fn get_now_playing_status(
+    pool: &SqlitePool,
+    room_id: &str,
+) -> Result<Option<NowPlayingStatus>> {
+    let Some(song) = get_now_playing(pool, room_id).await? else {
+        return Ok(None);
+    };
+
+    let total_score = sqlx::query_scalar!(
+        r#"SELECT COALESCE(SUM(decision), 0) as "total!: i64" FROM votes WHERE room_id = ? AND song_id = ?"#,
+        room_id,
+        song.id
+    )
+    .fetch_one(pool)
+    .await?;
+    let supporters = get_top_supporters(pool, room_id, &song.id).await?;
+
+    Ok(Some(NowPlayingStatus {
+        song,
+        total_score,
+        supporters,
+    }))
+}
+
+// Builds the full "who queued this" status view for a room: the currently-playing song and
+// every unplayed candidate, each with its top supporters.
+async fn get_room_status(pool: &SqlitePool, room_id: &str) -> Result<RoomStatus> {
+    let now_playing = get_now_playing_status(pool, room_id).await?;
+
+    let unplayed = sqlx::query!(
+        r#"
+        SELECT
+            s.id as "id!",
+            s.name as "name!",
+            COALESCE(SUM(v.decision), 0) as "total_score!: i64"
+        FROM songs s
+        LEFT JOIN votes v ON v.song_id = s.id AND v.room_id = ?
+        WHERE NOT EXISTS (
+            SELECT 1 FROM room_song_plays rsp
+            WHERE rsp.room_id = ? AND rsp.song_id = s.id
+        )
+        GROUP BY s.id
+        ORDER BY COALESCE(SUM(v.decision), 0) DESC;
+        "#,
+        room_id,
+        room_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut candidates = Vec::with_capacity(unplayed.len());
+    for song in unplayed {
+        let supporters = get_top_supporters(pool, room_id, &song.id).await?;
+        candidates.push(CandidateStatus {
+            id: song.id,
+            name: song.name,
+            total_score: song.total_score,
+            supporters,
+        });
+    }
+
+    Ok(RoomStatus {
+        now_playing,
+        candidates,
+    })
+}
+
+// Renders the HTML status page for a room.
+async fn status_page(path: web::Path<String>, data: web::Data<AppState>) -> Result<Status, Error> {
+    let room = get_room_by_slug(&data.db_pool, &path)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to look up room {}: {e}", *path);
+            actix_web::error::ErrorInternalServerError("Database error")
+        })?
+        .ok_or_else(|| actix_web::error::ErrorNotFound("Room not found"))?;
+
+    let RoomStatus {
+        now_playing,
+        candidates,
+    } = get_room_status(&data.db_pool, &room.id)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to build status for room {}: {e}", room.slug);
+            actix_web::error::ErrorInternalServerError("Could not load status")
+        })?;
+
+    Ok(Status {
+        slug: room.slug,
+        now_playing,
+        candidates,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct WhoAmI {
+    display_name: String,
+}
+
+// Lets a voter set the nickname shown in "championed by" attribution. Keyed off the existing
+// voter_id cookie rather than a body field, so a voter can't set someone else's nickname.
+async fn whoami_handler(
+    req: actix_web::HttpRequest,
+    data: web::Data<AppState>,
+    Form(form): Form<WhoAmI>,
+) -> impl Responder {
+    let mut jar = actix_web::cookie::CookieJar::new();
+    let voter_id = ensure_voter_id_cookie(&req, &mut jar);
+
+    let display_name = form.display_name.trim();
+    if display_name.is_empty() {
+        return HttpResponse::BadRequest().body("Display name cannot be empty");
+    }
+
+    let voter_id_string = voter_id.to_string();
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO voters (id, display_name) VALUES (?, ?)
+        ON CONFLICT(id) DO UPDATE SET display_name = excluded.display_name
+        "#,
+        voter_id_string,
+        display_name
+    )
+    .execute(&data.db_pool)
+    .await;
+
+    match result {
+        Ok(_) => {
+            let mut response_builder = HttpResponse::Ok();
+            for cookie in jar.delta() {
+                response_builder.cookie(cookie.clone());
+            }
+            response_builder.body("Nickname saved")
+        }
+        Err(e) => {
+            log::error!("Failed to save nickname for voter {voter_id}: {e}");
+            HttpResponse::InternalServerError().body("Could not save nickname")
+        }
+    }
+}
+
+async fn api_status_handler(path: web::Path<String>, data: web::Data<AppState>) -> HttpResponse {
+    let room = match api_find_room(&data.db_pool, &path).await {
+        Ok(room) => room,
+        Err(response) => return response,
+    };
+
+    match get_room_status(&data.db_pool, &room.id).await {
+        Ok(status) => ApiResponse::success(status),
+        Err(e) => {
+            log::error!("Failed to build status for room {}: {e}", room.slug);
+            ApiResponse::<()>::fatal("Could not load status")
+        }
+    }
+}
+
+// --- JSON API (/api/v1) ---
+// Mirrors the HTML handlers above but returns ApiResponse-wrapped JSON instead of rendered
+// partials, for the decoupled voter clients that can't drive htmx.
+
+// Looks up a room by slug or short-circuits with the appropriate ApiResponse error.
+async fn api_find_room(pool: &SqlitePool, slug: &str) -> Result<Room, HttpResponse> {
+    match get_room_by_slug(pool, slug).await {
+        Ok(Some(room)) => Ok(room),
+        Ok(None) => Err(ApiResponse::<()>::failure("Room not found")),
+        Err(e) => {
+            log::error!("Failed to look up room {slug}: {e}");
+            Err(ApiResponse::<()>::fatal("Database error"))
+        }
+    }
+}
+
+async fn api_queue_handler(
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> HttpResponse {
+    let room = match api_find_room(&data.db_pool, &path).await {
+        Ok(room) => room,
+        Err(response) => return response,
+    };
+
+    let voter_id = req
+        .cookie(VOTER_ID_COOKIE)
+        .and_then(|cookie| Uuid::parse_str(cookie.value()).ok())
+        .unwrap_or_else(Uuid::new_v4);
+
+    match get_candidates_with_scores(&data.db_pool, voter_id, &room.id, &data.metrics).await {
+        Ok(candidates) => ApiResponse::success(candidates),
+        Err(e) => {
+            log::error!(
+                "Failed to get candidates for API queue ({}): {e}",
+                room.slug
+            );
+            ApiResponse::<()>::fatal("Could not load queue")
+        }
+    }
+}
+
+async fn api_vote_handler(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+    Json(vote_data): Json<Vote>,
+) -> HttpResponse {
+    let room = match api_find_room(&data.db_pool, &path).await {
+        Ok(room) => room,
+        Err(response) => return response,
+    };
+
+    let Vote {
+        decision,
+        voter_id,
+        song_id,
+    } = vote_data;
+
+    if !(-127..=127).contains(&decision) {
+        log::warn!("Invalid decision value {decision} received from {voter_id}");
+        return ApiResponse::<()>::failure(format!("Invalid decision value {decision}"));
+    }
+
+    if let Err(e) = record_vote(&data.db_pool, &room.id, voter_id, &song_id, decision).await {
+        log::error!("Failed to record vote for song {song_id}: {e}");
+        return ApiResponse::<()>::fatal("Error saving vote");
+    }
+
+    data.metrics.record_vote(decision);
+    let _ = data.queue_events.send(QueueEvent::VoteCast {
+        room_id: room.id.clone(),
+        song_id: song_id.clone(),
+    });
+
+    match get_candidates_with_scores(&data.db_pool, voter_id, &room.id, &data.metrics).await {
+        Ok(candidates) => ApiResponse::success(candidates),
+        Err(e) => {
+            log::error!(
+                "Failed to get candidates after API vote ({}): {e}",
+                room.slug
+            );
+            ApiResponse::<()>::fatal("Error refreshing the queue after vote")
+        }
+    }
+}
+
+async fn api_next_handler(path: web::Path<String>, data: web::Data<AppState>) -> HttpResponse {
+    let room = match api_find_room(&data.db_pool, &path).await {
+        Ok(room) => room,
+        Err(response) => return response,
+    };
+
+    match select_and_mark_next_song(&data, &room).await {
+        Ok(Some(song)) => ApiResponse::success(song),
+        Ok(None) => ApiResponse::<()>::failure("No unplayed songs available"),
+        Err(e) => {
+            log::error!("Failed to select next song for room {}: {e}", room.slug);
+            ApiResponse::<()>::fatal("Database error finding next song")
+        }
+    }
+}
+
+// Now-playing is tracked per room (see room_song_plays), so there's no single global value to
+// report; this mirrors the other /api/v1/r/{slug}/* endpoints instead of a flat path.
+async fn api_now_playing_handler(
+    path: web::Path<String>,
+    data: web::Data<AppState>,
+) -> HttpResponse {
+    let room = match api_find_room(&data.db_pool, &path).await {
+        Ok(room) => room,
+        Err(response) => return response,
+    };
+
+    match get_now_playing(&data.db_pool, &room.id).await {
+        Ok(Some(song)) => ApiResponse::success(song),
+        Ok(None) => ApiResponse::<()>::failure("No song has been played yet"),
+        Err(e) => {
+            log::error!(
+                "Failed to look up now-playing song for room {}: {e}",
+                room.slug
+            );
+            ApiResponse::<()>::fatal("Database error")
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
@@ -458,6 +1477,12 @@ async fn main() -> Result<()> {
         log::info!("Created database directory: {db_dir:?}");
     }
 
+    let covers_dir = Path::new(COVERS_DIRECTORY);
+    if !covers_dir.exists() {
+        fs::create_dir(covers_dir).await?;
+        log::info!("Created cover art directory: {covers_dir:?}");
+    }
+
     //let db_file = db_dir.join("votes.db");
     let pool = SqlitePoolOptions::new()
         .max_connections(5)
@@ -489,19 +1514,77 @@ async fn main() -> Result<()> {
     };
     log::info!("Listening on {ADDR}");
 
+    // Capacity is generous headroom between ticks; lagging subscribers fall back to a full
+    // refresh rather than missing updates silently.
+    let (queue_events, _) = broadcast::channel::<QueueEvent>(64);
+    let metrics = Metrics::new().context("Failed to initialize Prometheus registry")?;
+    let metrics_enabled = std::env::var(METRICS_ENABLED_ENV).is_ok();
+    if metrics_enabled {
+        log::info!("Metrics enabled: exposing GET /metrics");
+    }
+
+    let selection_mode = match std::env::var(SELECTION_MODE_ENV).as_deref() {
+        Ok("weighted") => {
+            // Must be positive and finite: weighted_pick divides by temperature, so a
+            // non-positive value produces NaN/infinite weights rather than the "more greedy"
+            // behavior the name suggests.
+            let temperature = match std::env::var(SELECTION_TEMPERATURE_ENV) {
+                Ok(raw) => match raw.parse::<f64>() {
+                    Ok(v) if v > 0.0 && v.is_finite() => v,
+                    _ => {
+                        log::warn!(
+                            "{SELECTION_TEMPERATURE_ENV}={raw:?} is not a positive finite number; falling back to {DEFAULT_SELECTION_TEMPERATURE}"
+                        );
+                        DEFAULT_SELECTION_TEMPERATURE
+                    }
+                },
+                Err(_) => DEFAULT_SELECTION_TEMPERATURE,
+            };
+            log::info!("Next-song selection: weighted-random (temperature={temperature})");
+            SelectionMode::WeightedRandom { temperature }
+        }
+        _ => {
+            log::info!("Next-song selection: greedy");
+            SelectionMode::Greedy
+        }
+    };
+
     HttpServer::new(move || {
-        App::new()
+        let app = App::new()
             .wrap(actix_web::middleware::Logger::default())
             .app_data(web::Data::new(AppState {
                 db_pool: pool.clone(),
+                queue_events: queue_events.clone(),
+                metrics: metrics.clone(),
+                selection_mode,
             }))
-            .route("/", web::get().to(queue_page))
-            .route("/vote", web::post().to(vote))
-            .route("/host", web::get().to(host_page))
-            .route("/queue", web::get().to(queue_content_handler))
-            .route("/next", web::get().to(next_song_handler))
+            .route("/", web::get().to(index_handler))
+            .route("/rooms", web::post().to(create_room_handler))
+            .route("/r/{slug}", web::get().to(queue_page))
+            .route("/r/{slug}/vote", web::post().to(vote))
+            .route("/r/{slug}/host", web::get().to(host_page))
+            .route("/r/{slug}/queue", web::get().to(queue_content_handler))
+            .route("/r/{slug}/events", web::get().to(events_handler))
+            .route("/r/{slug}/next", web::get().to(next_song_handler))
+            .route("/r/{slug}/status", web::get().to(status_page))
+            .route("/whoami", web::post().to(whoami_handler))
+            .route("/cover/{id}", web::get().to(cover_handler))
+            .route("/api/v1/r/{slug}/queue", web::get().to(api_queue_handler))
+            .route("/api/v1/r/{slug}/vote", web::post().to(api_vote_handler))
+            .route("/api/v1/r/{slug}/next", web::get().to(api_next_handler))
+            .route("/api/v1/r/{slug}/status", web::get().to(api_status_handler))
+            .route(
+                "/api/v1/r/{slug}/now-playing",
+                web::get().to(api_now_playing_handler),
+            )
             .service(actix_files::Files::new("/static", "./static"))
-            .service(actix_files::Files::new("/songs", MUSIC_DIRECTORY))
+            .service(actix_files::Files::new("/songs", MUSIC_DIRECTORY));
+
+        if metrics_enabled {
+            app.route("/metrics", web::get().to(metrics_handler))
+        } else {
+            app
+        }
     })
     .bind(ADDR)?
     .run()
@@ -509,3 +1592,33 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(id: &str, total_score: f64) -> ScoredSong {
+        ScoredSong {
+            id: id.to_string(),
+            name: id.to_string(),
+            total_score,
+        }
+    }
+
+    #[test]
+    fn weighted_pick_returns_none_for_empty_pool() {
+        assert!(weighted_pick(&[], 1.0).is_none());
+    }
+
+    #[test]
+    fn weighted_pick_approaches_greedy_as_temperature_shrinks() {
+        let candidates = vec![song("low", 1.0), song("high", 100.0)];
+
+        // With a very small (but still positive, per the SELECTION_TEMPERATURE_ENV guard)
+        // temperature, the softmax weights collapse almost entirely onto the top scorer.
+        for _ in 0..20 {
+            let picked = weighted_pick(&candidates, 0.001).expect("non-empty pool");
+            assert_eq!(picked.id, "high");
+        }
+    }
+}